@@ -1,5 +1,5 @@
 use rustc::lint::*;
-use syntax::codemap::mk_sp;
+use syntax::codemap::{mk_sp, Span};
 use syntax::ast;
 use utils::{differing_macro_contexts, in_macro, snippet_opt, span_note_and_lint};
 use syntax::ptr::P;
@@ -46,12 +46,52 @@ declare_lint! {
     "suspicious formatting of `else if`"
 }
 
+/// **What it does:** Checks for formatting of a binary operator that is
+/// glued to a following unary operator.
+///
+/// **Why is this bad?** This looks like a single, unfamiliar operator and is
+/// almost always a typo.
+///
+/// **Known problems:** None.
+///
+/// **Example:**
+/// ```rust,ignore
+/// if foo &&! bar { } // this should be `foo && !bar`
+/// ```
+declare_lint! {
+    pub SUSPICIOUS_UNARY_OP_FORMATTING,
+    Warn,
+    "suspicious formatting of unary `-`, `*` or `!`, preceded by a binary operator"
+}
+
+/// **What it does:** Checks for possible missing commas in array literals.
+///
+/// **Why is this bad?** The array element `-3` followed by a line break and
+/// `-4` parses as `-3 - 4`, silently collapsing two elements into one. This
+/// is a common mistake when editing columnar numeric tables.
+///
+/// **Known problems:** None.
+///
+/// **Example:**
+/// ```rust,ignore
+/// let a = &[
+///     -1, -2, -3 // <= no comma here
+///     -4, -5, -6
+/// ];
+/// ```
+declare_lint! {
+    pub POSSIBLE_MISSING_COMMA,
+    Warn,
+    "possible missing comma in array literal"
+}
+
 #[derive(Copy,Clone)]
 pub struct Formatting;
 
 impl LintPass for Formatting {
     fn get_lints(&self) -> LintArray {
-        lint_array![SUSPICIOUS_ASSIGNMENT_FORMATTING, SUSPICIOUS_ELSE_FORMATTING]
+        lint_array![SUSPICIOUS_ASSIGNMENT_FORMATTING, SUSPICIOUS_ELSE_FORMATTING, SUSPICIOUS_UNARY_OP_FORMATTING,
+                    POSSIBLE_MISSING_COMMA]
     }
 }
 
@@ -71,6 +111,8 @@ impl EarlyLintPass for Formatting {
     fn check_expr(&mut self, cx: &EarlyContext, expr: &ast::Expr) {
         check_assign(cx, expr);
         check_else_if(cx, expr);
+        check_unary_op(cx, expr);
+        check_missing_comma(cx, expr);
     }
 }
 
@@ -100,10 +142,80 @@ fn check_assign(cx: &EarlyContext, expr: &ast::Expr) {
     }
 }
 
+/// Implementation of the `SUSPICIOUS_UNARY_OP_FORMATTING` lint.
+fn check_unary_op(cx: &EarlyContext, expr: &ast::Expr) {
+    if let ast::ExprKind::Binary(ref binop, ref lhs, ref rhs) = expr.node {
+        if !differing_macro_contexts(lhs.span, rhs.span) && !in_macro(cx, lhs.span) {
+            if let ast::ExprKind::Unary(unop, ref sub_rhs) = rhs.node {
+                if let Some(gap_snippet) = snippet_opt(cx, mk_sp(lhs.span.hi, sub_rhs.span.lo)) {
+                    let binop_str = binop.node.to_string();
+                    let unop_str = ast::UnOp::to_string(unop);
+                    let glued = format!("{}{}", binop_str, unop_str);
+                    let trimmed = gap_snippet.trim_left();
+
+                    if trimmed.starts_with(&glued[..]) && trimmed[glued.len()..].starts_with(' ') {
+                        let op_span = mk_sp(lhs.span.hi, sub_rhs.span.lo);
+                        span_note_and_lint(cx,
+                                           SUSPICIOUS_UNARY_OP_FORMATTING,
+                                           op_span,
+                                           &format!("this looks like you are trying to use `{binop} {unop}..`, but \
+                                                     the lack of space makes it look like `{glued}..`",
+                                                    binop = binop_str,
+                                                    unop = unop_str,
+                                                    glued = glued),
+                                           op_span,
+                                           &format!("to remove this lint, add a space after `{binop}`",
+                                                    binop = binop_str));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Implementation of the `POSSIBLE_MISSING_COMMA` lint.
+fn check_missing_comma(cx: &EarlyContext, expr: &ast::Expr) {
+    if let ast::ExprKind::Array(ref elements) = expr.node {
+        for element in elements {
+            if let ast::ExprKind::Binary(ref binop, ref lhs, ref rhs) = element.node {
+                if is_unary_prefix_op(binop.node) && !differing_macro_contexts(lhs.span, rhs.span) &&
+                   !in_macro(cx, lhs.span) {
+                    let before_op = mk_sp(lhs.span.hi, binop.span.lo);
+                    let after_op = mk_sp(binop.span.hi, rhs.span.lo);
+
+                    if let (Some(before_snippet), Some(after_snippet)) =
+                        (snippet_opt(cx, before_op), snippet_opt(cx, after_op)) {
+                        // the operator is on its own line, right up against the next value: this
+                        // looks like a prefix on a new element, not an infix operator
+                        if before_snippet.contains('\n') && !after_snippet.starts_with(' ') {
+                            span_note_and_lint(cx,
+                                               POSSIBLE_MISSING_COMMA,
+                                               binop.span,
+                                               "possibly missing a comma here",
+                                               binop.span,
+                                               "to remove this lint, add a comma or write this on the same line as \
+                                                the previous element");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns true for binary operators that are also valid unary prefix operators (`-`, `*`, `&`).
+fn is_unary_prefix_op(op: ast::BinOpKind) -> bool {
+    match op {
+        ast::BinOpKind::Sub | ast::BinOpKind::Mul | ast::BinOpKind::BitAnd => true,
+        _ => false,
+    }
+}
+
 /// Implementation of the `SUSPICIOUS_ELSE_FORMATTING` lint for weird `else if`.
 fn check_else_if(cx: &EarlyContext, expr: &ast::Expr) {
     if let Some((then, &Some(ref else_))) = unsugar_if(expr) {
-        if unsugar_if(else_).is_some() && !differing_macro_contexts(then.span, else_.span) && !in_macro(cx, then.span) {
+        if unsugar_if(else_).is_some() && is_span_if(cx, else_.span) &&
+           !differing_macro_contexts(then.span, else_.span) && !in_macro(cx, then.span) {
             // this will be a span from the closing ‘}’ of the “then” block (excluding) to the
             // “if” of the “else if” block (excluding)
             let else_span = mk_sp(then.span.hi, else_.span.lo);
@@ -130,7 +242,7 @@ fn check_else_if(cx: &EarlyContext, expr: &ast::Expr) {
 /// Implementation of the `SUSPICIOUS_ELSE_FORMATTING` lint for consecutive ifs.
 fn check_consecutive_ifs(cx: &EarlyContext, first: &ast::Expr, second: &ast::Expr) {
     if !differing_macro_contexts(first.span, second.span) && !in_macro(cx, first.span) &&
-       unsugar_if(first).is_some() && unsugar_if(second).is_some() {
+       unsugar_if(first).is_some() && unsugar_if(second).is_some() && is_span_if(cx, second.span) {
         // where the else would be
         let else_span = mk_sp(first.span.hi, second.span.lo);
 
@@ -156,3 +268,50 @@ fn unsugar_if(expr: &ast::Expr) -> Option<(&P<ast::Block>, &Option<P<ast::Expr>>
         _ => None,
     }
 }
+
+/// Checks whether the source at `span` really starts with the `if` keyword, ignoring any
+/// leading whitespace, line comments (`//`) and (possibly nested) block comments (`/* */`).
+/// Desugaring and macro expansion can shift a span so that it no longer points at the token we
+/// expect, so this confirms it before we trust it.
+fn is_span_if(cx: &EarlyContext, span: Span) -> bool {
+    match snippet_opt(cx, span) {
+        Some(snippet) => {
+            let mut trivia = snippet.trim_left();
+
+            loop {
+                if trivia.starts_with("//") {
+                    trivia = match trivia.find('\n') {
+                        Some(pos) => trivia[pos + 1..].trim_left(),
+                        None => "",
+                    };
+                } else if trivia.starts_with("/*") {
+                    let mut depth = 0;
+                    let mut end = trivia.len();
+                    let mut chars = trivia.char_indices();
+
+                    while let Some((i, c)) = chars.next() {
+                        if c == '/' && trivia[i + 1..].starts_with('*') {
+                            depth += 1;
+                            chars.next();
+                        } else if c == '*' && trivia[i + 1..].starts_with('/') {
+                            depth -= 1;
+                            chars.next();
+                            if depth == 0 {
+                                end = i + 2;
+                                break;
+                            }
+                        }
+                    }
+
+                    trivia = trivia[end..].trim_left();
+                } else {
+                    break;
+                }
+            }
+
+            trivia.starts_with("if") &&
+            !trivia["if".len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        },
+        None => false,
+    }
+}
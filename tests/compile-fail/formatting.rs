@@ -0,0 +1,83 @@
+#![feature(plugin)]
+#![plugin(clippy)]
+
+#![deny(suspicious_else_formatting)]
+#![deny(suspicious_unary_op_formatting)]
+#![deny(possible_missing_comma)]
+#![allow(dead_code, unused_variables, blacklisted_name)]
+
+macro_rules! make_if {
+    () => {
+        if true {
+        }
+    }
+}
+
+fn unary_op_formatting() {
+    let foo = true;
+    let bar = true;
+    let x = 1;
+
+    // ok, there is a space after the binary operator and none between the unary operator and its operand
+    if foo && !bar {
+    }
+    if x == -1 {
+    }
+
+    // the binary operator is glued to the following unary operator
+    if foo &&! bar {
+        //~^ ERROR this looks like you are trying to use `&& !..`
+    }
+    if x ==- 1 {
+        //~^ ERROR this looks like you are trying to use `== -..`
+    }
+}
+
+fn missing_comma() {
+    // ok, no comma needed, this is a single two-line expression
+    let ok = &[
+        1
+        - 2
+    ];
+
+    // the columnar layout makes the missing comma look like a unary prefix on the next line
+    let bad = &[
+        -1, -2, -3
+        -4, -5, -6
+        //~^ ERROR possibly missing a comma here
+    ];
+}
+
+fn else_if_formatting() {
+    // a genuine `else if` still lints, even with the new `is_span_if` gate
+    if true {
+    } else
+    if true {
+    }
+    //~^^^ ERROR this is an `else if` but the formatting might hide it
+
+    // a genuine pair of standalone `if`s with a missing `else` between them still lints
+    if true {
+    } if true {
+    }
+    //~^^ ERROR this looks like an `else if` but the `else` is missing
+
+    // not a false positive: the `if let` starts on its own line
+    let x = Some(1);
+    if true {
+    }
+    if let Some(_) = x {
+    }
+
+    // not a false positive: the second `if` comes from a macro, in a different macro context
+    // from the first, and starts on its own line
+    if true {
+    }
+    make_if!();
+}
+
+fn main() {
+    unary_op_formatting();
+    missing_comma();
+    else_if_formatting();
+}